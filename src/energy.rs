@@ -17,27 +17,221 @@ pub struct Calorie(pub u32);
 
 pub type BTU = u32;
 
+/// An error that can occur while converting between energy units.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum ConversionError {
+    /// The conversion would overflow the target integer type.
+    Overflow,
+    /// The conversion is exact only up to a remainder, which is reported here so the caller can
+    /// decide whether the truncation is acceptable.
+    PrecisionLoss { remainder: u32 },
+}
+
+/// A non-panicking, "best effort" conversion that truncates any remainder. Prefer
+/// [`CheckedUnit::checked_to_btu`] when the precision of a conversion matters.
 impl From<Joule> for BTU {
     fn from(j: Joule) -> Self {
         j.0 / 1055
     }
 }
 
+/// A non-panicking, "best effort" conversion from `BTU` to `Joule`, saturating instead of
+/// wrapping on overflow. Prefer [`CheckedUnit::checked_from_btu`] when the overflow of a
+/// conversion matters.
 impl From<BTU> for Joule {
     fn from(b: BTU) -> Self {
-        Self(b * 1055)
+        Self(b.saturating_mul(1055))
     }
 }
 
+/// A non-panicking, "best effort" conversion that truncates any remainder. Prefer
+/// [`CheckedUnit::checked_to_btu`] when the precision of a conversion matters.
 impl From<Calorie> for BTU {
     fn from(c: Calorie) -> Self {
         c.0 / 251
     }
 }
 
+/// A non-panicking, "best effort" conversion from `BTU` to `Calorie`, saturating instead of
+/// wrapping on overflow. Prefer [`CheckedUnit::checked_from_btu`] when the overflow of a
+/// conversion matters.
 impl From<BTU> for Calorie {
     fn from(b: BTU) -> Self {
-        Calorie(b * 251)
+        Calorie(b.saturating_mul(251))
+    }
+}
+
+/// A checked round-trip to and from `BTU`, reported through [`ConversionError`] rather than the
+/// silent truncation/overflow of the plain [`From`] conversions above.
+///
+/// This is a bare trait rather than `TryFrom`/`TryInto` impls because the standard library
+/// already provides a blanket `TryFrom<U> for T where U: Into<T>` covering every pair above that
+/// has a [`From`] impl, which would conflict with a manual one.
+pub trait CheckedUnit: Sized {
+    fn checked_to_btu(self) -> Result<BTU, ConversionError>;
+    fn checked_from_btu(b: BTU) -> Result<Self, ConversionError>;
+}
+
+impl CheckedUnit for Joule {
+    fn checked_to_btu(self) -> Result<BTU, ConversionError> {
+        let remainder = self.0 % 1055;
+        if remainder != 0 {
+            return Err(ConversionError::PrecisionLoss { remainder });
+        }
+        Ok(self.0 / 1055)
+    }
+
+    fn checked_from_btu(b: BTU) -> Result<Self, ConversionError> {
+        b.checked_mul(1055)
+            .map(Self)
+            .ok_or(ConversionError::Overflow)
+    }
+}
+
+impl CheckedUnit for Calorie {
+    fn checked_to_btu(self) -> Result<BTU, ConversionError> {
+        let remainder = self.0 % 251;
+        if remainder != 0 {
+            return Err(ConversionError::PrecisionLoss { remainder });
+        }
+        Ok(self.0 / 251)
+    }
+
+    fn checked_from_btu(b: BTU) -> Result<Self, ConversionError> {
+        b.checked_mul(251)
+            .map(Calorie)
+            .ok_or(ConversionError::Overflow)
+    }
+}
+
+impl CheckedUnit for BTU {
+    fn checked_to_btu(self) -> Result<BTU, ConversionError> {
+        Ok(self)
+    }
+
+    fn checked_from_btu(b: BTU) -> Result<Self, ConversionError> {
+        Ok(b)
+    }
+}
+
+/// The canonical unit that energy quantities are converted through internally.
+///
+/// `PER_BTU` is `1055 * 251` — the least common multiple of the Joule-per-BTU and Calorie-per-BTU
+/// factors used by this module's `From` impls, not an arbitrary round number like "milli-BTU".
+/// That's what makes the carrier actually lossless: converting a `Joule` or `Calorie` in means
+/// multiplying by an exact integer factor (`251` or `1055` respectively), so converting back out
+/// is an exact division with no remainder, for *any* value — not just ones that happen to already
+/// be a multiple of 1055 or 251.
+#[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub struct CanonicalUnit(pub u64);
+
+impl CanonicalUnit {
+    /// How many `CanonicalUnit` make up a single `BTU`: the LCM of `1055` and `251`.
+    pub const PER_BTU: u64 = 1055 * 251;
+
+    /// Add two canonical quantities, failing on overflow rather than wrapping.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(CanonicalUnit)
+    }
+
+    /// Subtract two canonical quantities, failing if `other` is larger than `self`.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(CanonicalUnit)
+    }
+
+    /// Scale a canonical quantity by a percentage, saturating at `100`.
+    pub fn mul_percent(self, e: u8) -> Self {
+        let e = e.min(100) as u64;
+        CanonicalUnit(self.0.saturating_mul(e) / 100)
+    }
+}
+
+/// An energy unit that can be converted to and from the canonical [`CanonicalUnit`] carrier.
+///
+/// This is what lets [`ProvideEnergy`] and the fuel mixers below do all of their arithmetic in a
+/// single, lossless unit and only convert to `Self` at the boundary.
+pub trait EnergyUnit: Sized + Copy {
+    /// Convert into the canonical carrier.
+    fn to_canonical(self) -> CanonicalUnit;
+
+    /// Convert from the canonical carrier, saturating rather than failing if the value doesn't
+    /// fit in `Self`.
+    fn from_canonical(m: CanonicalUnit) -> Self;
+
+    /// Convert from the canonical carrier, failing with a [`ConversionError`] if the value
+    /// doesn't fit in `Self`.
+    fn try_from_canonical(m: CanonicalUnit) -> Result<Self, ConversionError>;
+}
+
+/// `CanonicalUnit::PER_BTU / 1055 == 251` exactly, so scaling a `Joule` count by this factor to
+/// get a canonical one (and back) never loses precision.
+const JOULE_PER_CANONICAL_FACTOR: u64 = CanonicalUnit::PER_BTU / 1055;
+
+/// `CanonicalUnit::PER_BTU / 251 == 1055` exactly, so scaling a `Calorie` count by this factor to
+/// get a canonical one (and back) never loses precision.
+const CALORIE_PER_CANONICAL_FACTOR: u64 = CanonicalUnit::PER_BTU / 251;
+
+impl EnergyUnit for Joule {
+    fn to_canonical(self) -> CanonicalUnit {
+        CanonicalUnit((self.0 as u64).saturating_mul(JOULE_PER_CANONICAL_FACTOR))
+    }
+
+    fn from_canonical(m: CanonicalUnit) -> Self {
+        Joule(u32::try_from(m.0 / JOULE_PER_CANONICAL_FACTOR).unwrap_or(u32::MAX))
+    }
+
+    fn try_from_canonical(m: CanonicalUnit) -> Result<Self, ConversionError> {
+        let remainder = m.0 % JOULE_PER_CANONICAL_FACTOR;
+        if remainder != 0 {
+            return Err(ConversionError::PrecisionLoss {
+                remainder: remainder as u32,
+            });
+        }
+        u32::try_from(m.0 / JOULE_PER_CANONICAL_FACTOR)
+            .map(Joule)
+            .map_err(|_| ConversionError::Overflow)
+    }
+}
+
+impl EnergyUnit for Calorie {
+    fn to_canonical(self) -> CanonicalUnit {
+        CanonicalUnit((self.0 as u64).saturating_mul(CALORIE_PER_CANONICAL_FACTOR))
+    }
+
+    fn from_canonical(m: CanonicalUnit) -> Self {
+        Calorie(u32::try_from(m.0 / CALORIE_PER_CANONICAL_FACTOR).unwrap_or(u32::MAX))
+    }
+
+    fn try_from_canonical(m: CanonicalUnit) -> Result<Self, ConversionError> {
+        let remainder = m.0 % CALORIE_PER_CANONICAL_FACTOR;
+        if remainder != 0 {
+            return Err(ConversionError::PrecisionLoss {
+                remainder: remainder as u32,
+            });
+        }
+        u32::try_from(m.0 / CALORIE_PER_CANONICAL_FACTOR)
+            .map(Calorie)
+            .map_err(|_| ConversionError::Overflow)
+    }
+}
+
+impl EnergyUnit for BTU {
+    fn to_canonical(self) -> CanonicalUnit {
+        CanonicalUnit((self as u64).saturating_mul(CanonicalUnit::PER_BTU))
+    }
+
+    fn from_canonical(m: CanonicalUnit) -> Self {
+        u32::try_from(m.0 / CanonicalUnit::PER_BTU).unwrap_or(u32::MAX)
+    }
+
+    fn try_from_canonical(m: CanonicalUnit) -> Result<Self, ConversionError> {
+        let remainder = m.0 % CanonicalUnit::PER_BTU;
+        if remainder != 0 {
+            return Err(ConversionError::PrecisionLoss {
+                remainder: remainder as u32,
+            });
+        }
+        u32::try_from(m.0 / CanonicalUnit::PER_BTU).map_err(|_| ConversionError::Overflow)
     }
 }
 
@@ -48,7 +242,7 @@ pub trait Fuel {
     /// The output unit of the energy density.
     ///
     /// Think about this: why did we chose this to be an associated type rather than a generic?
-    type Output: Into<BTU> + From<BTU>;
+    type Output: Into<BTU> + From<BTU> + CheckedUnit + EnergyUnit;
 
     /// The amount of energy contained in a single unit of fuel.
     fn energy_density() -> Self::Output;
@@ -82,6 +276,10 @@ impl Fuel for Uranium {
 pub struct FuelContainer<F: Fuel> {
     /// The amount of fuel.
     amount: u32,
+    /// A runtime-computed energy density overriding `F::energy_density()`, set by [`blend`] when
+    /// two real containers are mixed (as opposed to `Mixed`/`CustomMixed`, which only describe a
+    /// type-level recipe with no backing amount).
+    runtime_density: Option<CanonicalUnit>,
     /// NOTE: Fuel doesn't really have any methods that require `&self` on it,
     /// so any information that we can get, we can get from `F` as **TYPE**, we don't really need
     /// to store an instance of `F`, like `fuel: F` as a struct field. But to satisfy the compiler,
@@ -94,10 +292,35 @@ impl<F: Fuel> FuelContainer<F> {
     pub fn new(amount: u32) -> Self {
         Self {
             amount,
+            runtime_density: None,
             // _marker: Default::default(),
             _marker: Default::default(),
         }
     }
+
+    /// Build a container carrying an explicit, runtime-computed density instead of `F`'s type-level
+    /// one. Used by [`blend`] to back a [`CustomMixed`] container with the actual weighted average
+    /// of the two real tanks that were combined.
+    fn with_runtime_density(amount: u32, density: CanonicalUnit) -> Self {
+        Self {
+            amount,
+            runtime_density: Some(density),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The energy density providers should use for this container: the runtime-computed one if
+    /// [`blend`] set one, otherwise `F`'s own type-level density.
+    pub fn energy_density(&self) -> CanonicalUnit {
+        self.runtime_density
+            .unwrap_or_else(|| F::energy_density().to_canonical())
+    }
+
+    /// The total energy held in this container: [`Self::energy_density`] times `amount`, in
+    /// canonical units.
+    pub fn total_energy(&self) -> CanonicalUnit {
+        CanonicalUnit(self.energy_density().0.saturating_mul(self.amount as u64))
+    }
 }
 
 /// Something that can provide energy from a given `F` fuel type, like a power-plant.
@@ -120,69 +343,147 @@ pub trait ProvideEnergy<F: Fuel> {
     /// percent. If an efficiency above 100 is supplied, the code should treat it as 100. That is to
     /// say that the efficiency is "saturating" at 100%.
     ///
+    /// Unlike [`ProvideEnergy::provide_energy_with_efficiency_saturating`], this fails with a
+    /// [`ConversionError`] rather than silently truncating or wrapping when the underlying unit
+    /// conversions can't represent the result exactly.
+    ///
+    /// This goes through [`CheckedUnit`] rather than the canonical `CanonicalUnit` path, so
+    /// exactness is judged in whole `BTU`: an energy that isn't a whole number of `BTU` (such as
+    /// `Joule(7)`), or a `BTU` value that `e` percent doesn't divide evenly, is reported as
+    /// [`ConversionError::PrecisionLoss`] even though the canonical carrier could hold it exactly.
+    ///
     /// This method must be provided as it will be the same in all implementations.
-    fn provide_energy_with_efficiency(&self, f: FuelContainer<F>, e: u8) -> <F as Fuel>::Output {
-        let efficiency = if e > 100 { 100 } else { e };
+    fn provide_energy_with_efficiency(
+        &self,
+        f: FuelContainer<F>,
+        e: u8,
+    ) -> Result<<F as Fuel>::Output, ConversionError> {
         let energy = self.provide_energy(f);
-        let energy_in_btu = energy.into();
-        let adjusted_energy = (energy_in_btu as f64 * efficiency as f64 / 100.0) as u32;
-        F::Output::from(adjusted_energy)
+        let as_btu = energy.checked_to_btu()?;
+
+        let e = e.min(100) as u64;
+        let scaled = as_btu as u64 * e;
+        let remainder = scaled % 100;
+        if remainder != 0 {
+            return Err(ConversionError::PrecisionLoss {
+                remainder: remainder as u32,
+            });
+        }
+
+        let adjusted = u32::try_from(scaled / 100).map_err(|_| ConversionError::Overflow)?;
+        F::Output::checked_from_btu(adjusted)
     }
 
     /// Same as [`ProvideEnergy::provide_energy_with_efficiency`], but with an efficiency of 100.
     ///
     /// This method must be provided as it will be the same in all implementations.
-    fn provide_energy_ideal(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+    fn provide_energy_ideal(
+        &self,
+        f: FuelContainer<F>,
+    ) -> Result<<F as Fuel>::Output, ConversionError> {
         self.provide_energy_with_efficiency(f, 100)
     }
+
+    /// Same as [`ProvideEnergy::provide_energy_with_efficiency`], but never fails: overflow and
+    /// precision loss are absorbed with saturating/truncating arithmetic instead of being
+    /// reported. The scaling is done on the canonical carrier, so nothing is truncated to whole
+    /// `BTU` along the way. For callers that don't care to distinguish a lossy result from an
+    /// exact one.
+    fn provide_energy_with_efficiency_saturating(
+        &self,
+        f: FuelContainer<F>,
+        e: u8,
+    ) -> <F as Fuel>::Output {
+        let energy = self.provide_energy(f);
+        let adjusted = energy.to_canonical().mul_percent(e);
+        F::Output::from_canonical(adjusted)
+    }
+
+    /// Same as [`ProvideEnergy::provide_energy_with_efficiency_saturating`], but with an
+    /// efficiency of 100.
+    fn provide_energy_ideal_saturating(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+        self.provide_energy_with_efficiency_saturating(f, 100)
+    }
 }
 
 /// A nuclear reactor that can only consume `Uranium` and provide energy with 99% efficiency.
 pub struct NuclearReactor;
 impl<F: Fuel> ProvideEnergy<F> for NuclearReactor {
     fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
-        let efficiency: u32 = 99;
-        let energy_density: u32 = f.amount * F::energy_density().into();
-        let adjusted_energy = energy_density * efficiency / 100;
-        F::Output::from(adjusted_energy)
+        let adjusted = f.total_energy().mul_percent(99);
+        F::Output::from_canonical(adjusted)
     }
 }
 
+/// Controls when [`InternalCombustion`] realizes the efficiency penalty once its decay counter
+/// crosses the `DECAY` threshold.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum DecayMode {
+    /// Apply the penalty up front, for the whole upcoming burst, the moment the threshold is
+    /// crossed.
+    Eager,
+    /// Defer the penalty: it only materializes on the next call that actually draws fuel, so a
+    /// call with a zero amount doesn't advance the decay counter or trigger it.
+    Lazy,
+}
+
 /// A combustion engine that can only consume `Diesel`.
 ///
 /// The `DECAY` const must be interpreted as such: per every `DECAY` times `provide_energy` is
 /// called on an instance of this type, the efficiency should reduce by one. The initial efficiency
-/// must be configurable with a `fn new(efficiency: u8) -> Self`.
+/// and [`DecayMode`] must be configurable with a `fn new(efficiency: u8, mode: DecayMode) -> Self`.
 pub struct InternalCombustion<const DECAY: u32> {
     efficiency: u8,
+    mode: DecayMode,
     count: RefCell<u32>,
+    /// Set in [`DecayMode::Lazy`] once the counter crosses `DECAY`; the penalty is realized on the
+    /// next fuel-consuming call instead of the one that set this flag.
+    pending_decay: RefCell<bool>,
 }
 
 impl<const DECAY: u32> InternalCombustion<DECAY> {
-    pub fn new(efficiency: u8) -> Self {
+    pub fn new(efficiency: u8, mode: DecayMode) -> Self {
         Self {
             efficiency: efficiency.min(100),
+            mode,
             count: RefCell::new(0),
+            pending_decay: RefCell::new(false),
         }
     }
-    pub fn update_count(&self, new_count: u32) {
-        *self.count.borrow_mut() = new_count + 1;
-    }
 }
 impl<const DECAY: u32, F: Fuel> ProvideEnergy<F> for InternalCombustion<DECAY> {
     fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
-        let mut new_count = *self.count.borrow_mut();
+        let burns_fuel = f.amount > 0;
         let mut efficiency = self.efficiency;
 
-        if new_count == DECAY {
-            new_count = 0;
-            efficiency = self.efficiency.saturating_sub(1);
+        match self.mode {
+            DecayMode::Eager => {
+                let mut count = *self.count.borrow();
+                if count == DECAY {
+                    count = 0;
+                    efficiency = self.efficiency.saturating_sub(1);
+                }
+                *self.count.borrow_mut() = count + 1;
+            }
+            DecayMode::Lazy => {
+                if burns_fuel {
+                    if *self.pending_decay.borrow() {
+                        efficiency = self.efficiency.saturating_sub(1);
+                        *self.pending_decay.borrow_mut() = false;
+                    }
+                    let mut count = *self.count.borrow() + 1;
+                    if count == DECAY {
+                        count = 0;
+                        *self.pending_decay.borrow_mut() = true;
+                    }
+                    *self.count.borrow_mut() = count;
+                }
+            }
         }
-        self.update_count(new_count);
-        let energy_in_btu = f.amount * F::energy_density().into();
-        let adjusted_energy = (energy_in_btu * (efficiency as u32)) / 100;
 
-        <F as Fuel>::Output::from(adjusted_energy)
+        let adjusted = f.total_energy().mul_percent(efficiency);
+
+        <F as Fuel>::Output::from_canonical(adjusted)
     }
 }
 
@@ -195,11 +496,228 @@ pub struct OmniGenerator<const EFFICIENCY: u8>;
 // NOTE: implement `ProvideEnergy` for `OmniGenerator` using only one `impl` block.
 impl<const EFFICIENCY: u8, F: Fuel> ProvideEnergy<F> for OmniGenerator<EFFICIENCY> {
     fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
-        let efficiency = if EFFICIENCY > 100 { 100 } else { EFFICIENCY };
-        let energy_density = F::energy_density().into();
-        let energy_in_btu = f.amount * energy_density;
-        let adjusted_energy = (energy_in_btu as f64 * efficiency as f64 / 100.0) as u32;
-        F::Output::from(adjusted_energy)
+        let adjusted = f.total_energy().mul_percent(EFFICIENCY);
+        F::Output::from_canonical(adjusted)
+    }
+}
+
+/// A wrapper around any [`ProvideEnergy`] that enforces a finite fuel budget, rather than letting
+/// the wrapped provider draw as much as it likes.
+///
+/// This is modeled on the fuel accounting used by wasmtime's `Store`: a `fuel_reserve` holds the
+/// initial budget in `BTU`, and `consumed` tracks how much of it has been drawn down so far.
+/// `refuel`/`set_fuel` always fold the outstanding budget into a fresh reserve and zero `consumed`
+/// rather than letting it go negative, so both fields stay plain, unsigned running totals and a
+/// single draw can never be misread as a refund.
+pub struct MeteredGenerator<F: Fuel, P: ProvideEnergy<F>> {
+    inner: P,
+    fuel_reserve: RefCell<u64>,
+    consumed: RefCell<u64>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Fuel, P: ProvideEnergy<F>> MeteredGenerator<F, P> {
+    pub fn new(inner: P, fuel_reserve: u64) -> Self {
+        Self {
+            inner,
+            fuel_reserve: RefCell::new(fuel_reserve),
+            consumed: RefCell::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The amount of fuel (in `BTU`) still available to draw on.
+    pub fn get_fuel(&self) -> u64 {
+        self.fuel_reserve
+            .borrow()
+            .saturating_sub(*self.consumed.borrow())
+    }
+
+    /// Reset the active budget to exactly `fuel`, discarding whatever was drawn down so far.
+    pub fn set_fuel(&self, fuel: u64) {
+        *self.fuel_reserve.borrow_mut() = fuel;
+        *self.consumed.borrow_mut() = 0;
+    }
+
+    /// Fold whatever is left of the current budget plus `extra` back into a fresh reserve.
+    ///
+    /// Returns whether any fuel remained in the budget before the top-up.
+    pub fn refuel(&self, extra: u64) -> bool {
+        let remaining = self.get_fuel();
+        self.set_fuel(remaining.saturating_add(extra));
+        remaining > 0
+    }
+}
+
+impl<F: Fuel, P: ProvideEnergy<F>> ProvideEnergy<F> for MeteredGenerator<F, P> {
+    fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+        let canonical_density = f.energy_density();
+        let density = BTU::from_canonical(canonical_density);
+        let requested_fuel = (f.amount as u64).saturating_mul(density as u64);
+
+        let drawable_amount = if requested_fuel <= self.get_fuel() {
+            f.amount
+        } else if density == 0 {
+            0
+        } else {
+            // Partial draw: only take as much amount as the remaining reserve allows.
+            (self.get_fuel() / density as u64) as u32
+        };
+
+        let debit = (drawable_amount as u64).saturating_mul(density as u64);
+        let consumed = self.consumed.borrow().saturating_add(debit);
+        *self.consumed.borrow_mut() = consumed;
+        self.inner
+            .provide_energy(FuelContainer::with_runtime_density(
+                drawable_amount,
+                canonical_density,
+            ))
+    }
+}
+
+/// A fuel whose flue gas has a well-defined dewpoint, needed by [`CondensingBoiler`] to know when
+/// latent heat starts condensing out of the exhaust.
+pub trait CondensingFuel: Fuel {
+    /// The dewpoint of this fuel's flue gas, in degrees Celsius.
+    const DEWPOINT_C: f64;
+}
+
+/// A fuel resembling mains natural gas for condensing purposes.
+pub struct NaturalGas;
+impl Fuel for NaturalGas {
+    type Output = BTU;
+    fn energy_density() -> Self::Output {
+        1000
+    }
+}
+impl CondensingFuel for NaturalGas {
+    const DEWPOINT_C: f64 = 52.2;
+}
+
+/// A fuel resembling LPG for condensing purposes.
+pub struct Lpg;
+impl Fuel for Lpg {
+    type Output = BTU;
+    fn energy_density() -> Self::Output {
+        2500
+    }
+}
+impl CondensingFuel for Lpg {
+    const DEWPOINT_C: f64 = 48.3;
+}
+
+/// A boiler whose efficiency isn't a constant but a function of the return-water temperature
+/// supplied at call time, modeling the extra latent-heat recovery ("condensing") gains that real
+/// boilers get below their flue-gas dewpoint.
+///
+/// Below the dewpoint the theoretical efficiency follows a quadratic curve; above it, the flue
+/// gas stops condensing and efficiency falls off linearly. A configurable `offset` subtracts a
+/// part-load correction from that theoretical curve.
+pub struct CondensingBoiler<F: CondensingFuel> {
+    offset: f64,
+    _marker: PhantomData<F>,
+}
+
+impl<F: CondensingFuel> CondensingBoiler<F> {
+    pub fn new(offset: f64) -> Self {
+        Self {
+            offset,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The default return temperature used by [`ProvideEnergy::provide_energy`].
+    const DEFAULT_RETURN_TEMP_C: f64 = 60.0;
+
+    /// The theoretical efficiency at return temperature `t` (°C), before the `offset` correction,
+    /// clamped into `[0, 1]`.
+    fn theoretical_efficiency(t: f64) -> f64 {
+        let dewpoint = F::DEWPOINT_C;
+        let eff = if t <= dewpoint {
+            -0.00007 * t * t + 0.0017 * t + 0.979
+        } else {
+            // Linear falloff above the dewpoint, chosen to be continuous with the quadratic
+            // branch at `t == dewpoint`.
+            let at_dewpoint = -0.00007 * dewpoint * dewpoint + 0.0017 * dewpoint + 0.979;
+            let c = at_dewpoint + 0.0006 * dewpoint;
+            -0.0006 * t + c
+        };
+        eff.clamp(0.0, 1.0)
+    }
+
+    /// Provide energy from `f`, using `t` (°C) as the return-water temperature.
+    pub fn provide_energy_at_return_temp(
+        &self,
+        f: FuelContainer<F>,
+        t: f64,
+    ) -> <F as Fuel>::Output {
+        let efficiency = (Self::theoretical_efficiency(t) - self.offset).clamp(0.0, 1.0);
+        let total = f.total_energy();
+        let adjusted = CanonicalUnit((total.0 as f64 * efficiency) as u64);
+        F::Output::from_canonical(adjusted)
+    }
+}
+
+impl<F: CondensingFuel> ProvideEnergy<F> for CondensingBoiler<F> {
+    fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+        self.provide_energy_at_return_temp(f, Self::DEFAULT_RETURN_TEMP_C)
+    }
+}
+
+/// The two outputs of a [`CombinedHeatPower`] plant, both expressed in `BTU`: the
+/// electrical energy generated, and the heat recovered alongside it rather than discarded.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct CogenOutput {
+    pub electrical: BTU,
+    pub heat: BTU,
+}
+
+/// A back-pressure-turbine style cogeneration plant: instead of discarding everything that isn't
+/// electrical output as waste, it reports the recoverable heat too.
+///
+/// Of the fuel energy, `efficiency` percent is usable at all; of *that* usable energy,
+/// `POWER_TO_HEAT` percent becomes electrical output and the remainder becomes recoverable heat.
+/// Both are computed in canonical units, so `electrical + heat <= fuel_energy` always holds.
+pub struct CombinedHeatPower<F: Fuel, const POWER_TO_HEAT: u8> {
+    efficiency: u8,
+    _marker: PhantomData<F>,
+}
+
+impl<F: Fuel, const POWER_TO_HEAT: u8> CombinedHeatPower<F, POWER_TO_HEAT> {
+    pub fn new(efficiency: u8) -> Self {
+        Self {
+            efficiency: efficiency.min(100),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The canonical `(electrical, heat)` split, shared by [`Self::provide_cogen`] and
+    /// [`ProvideEnergy::provide_energy`] so neither has to round-trip through the other's `BTU`
+    /// output.
+    fn split_canonical(&self, f: FuelContainer<F>) -> (CanonicalUnit, CanonicalUnit) {
+        let usable = f.total_energy().mul_percent(self.efficiency);
+        let electrical = usable.mul_percent(POWER_TO_HEAT);
+        let heat = usable.checked_sub(electrical).unwrap_or(CanonicalUnit(0));
+        (electrical, heat)
+    }
+
+    /// Produce both outputs of the plant, unlike [`ProvideEnergy::provide_energy`] which can only
+    /// report the electrical part.
+    pub fn provide_cogen(&self, f: FuelContainer<F>) -> CogenOutput {
+        let (electrical, heat) = self.split_canonical(f);
+        CogenOutput {
+            electrical: BTU::from_canonical(electrical),
+            heat: BTU::from_canonical(heat),
+        }
+    }
+}
+
+/// Reports only the electrical part of [`CombinedHeatPower::provide_cogen`], so existing
+/// [`ProvideEnergy`] callers keep working without knowing about the recoverable heat term.
+impl<F: Fuel, const POWER_TO_HEAT: u8> ProvideEnergy<F> for CombinedHeatPower<F, POWER_TO_HEAT> {
+    fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+        let (electrical, _heat) = self.split_canonical(f);
+        F::Output::from_canonical(electrical)
     }
 }
 
@@ -215,9 +733,10 @@ impl<F1: Fuel, F2: Fuel> Fuel for Mixed<F1, F2> {
     type Output = BTU;
 
     fn energy_density() -> Self::Output {
-        let density_f1 = F1::energy_density().into();
-        let density_f2 = F2::energy_density().into();
-        (density_f1 + density_f2) / 2
+        let density_f1 = F1::energy_density().to_canonical();
+        let density_f2 = F2::energy_density().to_canonical();
+        let average = CanonicalUnit((density_f1.0 + density_f2.0) / 2);
+        BTU::from_canonical(average)
     }
 }
 
@@ -236,11 +755,9 @@ impl<const C: u8, F1: Fuel, F2: Fuel> Fuel for CustomMixed<C, F1, F2> {
     type Output = BTU;
 
     fn energy_density() -> Self::Output {
-        let density_f1: BTU = F1::energy_density().into();
-        let density_f2: BTU = F2::energy_density().into();
-        let weighted_density_f1 = density_f1 * C as BTU / 100;
-        let weighted_density_f2 = density_f2 * (100 - C) as BTU / 100;
-        weighted_density_f1 + weighted_density_f2
+        let weighted_f1 = F1::energy_density().to_canonical().mul_percent(C);
+        let weighted_f2 = F2::energy_density().to_canonical().mul_percent(100 - C);
+        BTU::from_canonical(CanonicalUnit(weighted_f1.0 + weighted_f2.0))
     }
 }
 
@@ -263,14 +780,80 @@ pub fn omni_80_energy(amount: u32) -> BTU {
 pub trait IsRenewable {}
 impl IsRenewable for LithiumBattery {}
 
+/// A fuel that can be combined with another via [`blend`].
+///
+/// `IsRenewable` alone can't be queried generically — Rust has no specialization, so there's no
+/// way to ask "does `F` implement `IsRenewable`?" from code generic over `F: Fuel`. This trait
+/// exposes the same fact as a method instead, with a default of `false` so only the renewable
+/// fuels need to override it.
+pub trait BlendableFuel: Fuel {
+    /// Whether this fuel is renewable, mirroring its (or its absence of an) [`IsRenewable`] impl.
+    fn is_renewable() -> bool {
+        false
+    }
+}
+
+impl BlendableFuel for Diesel {}
+impl BlendableFuel for Uranium {}
+impl BlendableFuel for NaturalGas {}
+impl BlendableFuel for Lpg {}
+impl BlendableFuel for LithiumBattery {
+    fn is_renewable() -> bool {
+        true
+    }
+}
+
+/// An error produced by [`blend`] when two fuels can't honestly be combined into one.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum BlendError {
+    /// The two fuels disagree on renewability, so the blend can't give a single truthful answer
+    /// to `is_renewable()`.
+    Incompatible,
+}
+
+/// Combine two real, filled containers into one, rather than only describing a compile-time
+/// mixing recipe the way [`Mixed`]/[`CustomMixed`] do.
+///
+/// The resulting amount is `a.amount + b.amount`, and the resulting energy density is the
+/// amount-weighted average of the two inputs' densities in canonical units. `ratio` is only
+/// consulted when both containers are empty (so there's nothing to weight by): in that case it's
+/// used as `a`'s percent weight, the same convention [`CustomMixed`] uses for its `C`.
+///
+/// The `CustomMixed<0, F1, F2>` in the return type is just a type-level label; the blend's actual
+/// density always comes from the runtime-computed value stored in the returned container, not
+/// from `CustomMixed`'s own (unused) `energy_density`.
+pub fn blend<F1: BlendableFuel, F2: BlendableFuel>(
+    a: FuelContainer<F1>,
+    b: FuelContainer<F2>,
+    ratio: u8,
+) -> Result<FuelContainer<CustomMixed<0, F1, F2>>, BlendError> {
+    if F1::is_renewable() != F2::is_renewable() {
+        return Err(BlendError::Incompatible);
+    }
+
+    let density_a = a.energy_density();
+    let density_b = b.energy_density();
+    let total_amount = a.amount.saturating_add(b.amount);
+
+    let density = if total_amount == 0 {
+        let ratio = ratio.min(100);
+        CanonicalUnit(density_a.mul_percent(ratio).0 + density_b.mul_percent(100 - ratio).0)
+    } else {
+        let weighted =
+            (density_a.0 as u128) * (a.amount as u128) + (density_b.0 as u128) * (b.amount as u128);
+        CanonicalUnit((weighted / total_amount as u128) as u64)
+    };
+
+    Ok(FuelContainer::with_runtime_density(total_amount, density))
+}
+
 /// Define the following struct such that it only provides energy if the fuel is `IsRenewable`.
 ///
 /// It has perfect efficiency.
 pub struct GreenEngine<F: Fuel>(pub PhantomData<F>);
 impl<F: Fuel> ProvideEnergy<F> for GreenEngine<F> {
     fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
-        let efficiency = f.amount * F::energy_density().into();
-        efficiency.into()
+        F::Output::from_canonical(f.total_energy())
     }
 }
 
@@ -281,8 +864,7 @@ impl<F: Fuel> ProvideEnergy<F> for GreenEngine<F> {
 pub struct BritishEngine<F: Fuel>(pub PhantomData<F>);
 impl<F: Fuel> ProvideEnergy<F> for BritishEngine<F> {
     fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
-        let efficiency = f.amount * F::energy_density().into();
-        efficiency.into()
+        F::Output::from_canonical(f.total_energy())
     }
 }
 
@@ -300,6 +882,105 @@ mod tests {
         }
     }
 
+    #[test]
+    fn joule_to_btu_checked() {
+        assert_eq!(Joule(2110).checked_to_btu(), Ok(2));
+        assert_eq!(
+            Joule(2111).checked_to_btu(),
+            Err(ConversionError::PrecisionLoss { remainder: 1 })
+        );
+    }
+
+    #[test]
+    fn btu_to_joule_checked_overflow() {
+        assert_eq!(Joule::checked_from_btu(1_000), Ok(Joule(1_055_000)));
+        assert_eq!(
+            Joule::checked_from_btu(u32::MAX),
+            Err(ConversionError::Overflow)
+        );
+    }
+
+    #[test]
+    fn canonical_round_trip_is_lossless() {
+        assert_eq!(Joule(105_500).to_canonical(), CanonicalUnit(26_480_500));
+        assert_eq!(
+            Joule::from_canonical(CanonicalUnit(26_480_500)),
+            Joule(105_500)
+        );
+        assert_eq!(Calorie(50_200).to_canonical(), CanonicalUnit(52_961_000));
+        assert_eq!(
+            Calorie::from_canonical(CanonicalUnit(52_961_000)),
+            Calorie(50_200)
+        );
+    }
+
+    #[test]
+    fn canonical_round_trip_is_lossless_for_non_multiples() {
+        // Neither 100 nor 7 is a multiple of 1055, so a milli-BTU carrier (1000 per BTU, which
+        // is a multiple of neither 1055 nor 251) would truncate these on the way through. The
+        // LCM-based carrier must round-trip them exactly instead.
+        for joules in [1u32, 7, 100, 1054, 1056] {
+            assert_eq!(
+                Joule::from_canonical(Joule(joules).to_canonical()),
+                Joule(joules)
+            );
+        }
+        for calories in [1u32, 7, 100, 250, 252] {
+            assert_eq!(
+                Calorie::from_canonical(Calorie(calories).to_canonical()),
+                Calorie(calories)
+            );
+        }
+    }
+
+    #[test]
+    fn mul_percent_saturates_at_100() {
+        assert_eq!(CanonicalUnit(1000).mul_percent(150), CanonicalUnit(1000));
+        assert_eq!(CanonicalUnit(1000).mul_percent(50), CanonicalUnit(500));
+    }
+
+    #[test]
+    fn provide_energy_with_efficiency_is_exact_or_errors() {
+        let nr = NuclearReactor;
+        assert_eq!(
+            nr.provide_energy_with_efficiency(FuelContainer::<Uranium>::new(10), 100),
+            Ok(Joule(9_900 * 1055))
+        );
+        assert_eq!(
+            nr.provide_energy_with_efficiency_saturating(FuelContainer::<Uranium>::new(10), 100),
+            Joule(9_900 * 1055)
+        );
+    }
+
+    #[test]
+    fn provide_energy_with_efficiency_reports_precision_loss() {
+        struct OddDensity;
+        impl Fuel for OddDensity {
+            type Output = Joule;
+            fn energy_density() -> Self::Output {
+                Joule(7)
+            }
+        }
+        struct DensityPassthrough;
+        impl<F: Fuel> ProvideEnergy<F> for DensityPassthrough {
+            fn provide_energy(&self, _f: FuelContainer<F>) -> <F as Fuel>::Output {
+                F::energy_density()
+            }
+        }
+
+        // Joule(7) isn't a multiple of 1055, so the exact same precision loss that
+        // `Joule(7).checked_to_btu()` reports must surface here too, not just an `Overflow`.
+        assert_eq!(
+            Joule(7).checked_to_btu(),
+            Err(ConversionError::PrecisionLoss { remainder: 7 })
+        );
+        assert_eq!(
+            DensityPassthrough
+                .provide_energy_with_efficiency(FuelContainer::<OddDensity>::new(1), 100),
+            Err(ConversionError::PrecisionLoss { remainder: 7 })
+        );
+    }
+
     #[test]
     fn nuclear() {
         let nr = NuclearReactor;
@@ -317,11 +998,49 @@ mod tests {
 
     #[test]
     fn ic_1() {
-        let ic = InternalCombustion::<3>::new(120);
+        let ic = InternalCombustion::<3>::new(120, DecayMode::Eager);
+        assert_eq!(
+            ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+            1000
+        );
+        assert_eq!(
+            ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+            1000
+        );
+        assert_eq!(
+            ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+            1000
+        );
+        assert_eq!(
+            ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+            990
+        );
+    }
+
+    #[test]
+    fn ic_eager_zero_amount_call_still_advances_counter() {
+        let ic = InternalCombustion::<3>::new(100, DecayMode::Eager);
+        // Three calls (including a zero-amount one) cross the DECAY threshold, so the fourth call
+        // already pays the penalty, same as if all four had drawn fuel.
+        ic.provide_energy(FuelContainer::<Diesel>::new(0));
+        ic.provide_energy(FuelContainer::<Diesel>::new(10));
+        ic.provide_energy(FuelContainer::<Diesel>::new(10));
+        assert_eq!(
+            ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+            990
+        );
+    }
+
+    #[test]
+    fn ic_lazy_zero_amount_call_does_not_advance_counter() {
+        let ic = InternalCombustion::<3>::new(100, DecayMode::Lazy);
+        // The zero-amount call in the middle must not count towards DECAY, so it takes three
+        // *fuel-consuming* calls, not three calls total, to cross the threshold.
         assert_eq!(
             ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
             1000
         );
+        ic.provide_energy(FuelContainer::<Diesel>::new(0));
         assert_eq!(
             ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
             1000
@@ -330,6 +1049,8 @@ mod tests {
             ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
             1000
         );
+        // The threshold was crossed on the call above; the penalty only shows up on the next
+        // fuel-consuming call.
         assert_eq!(
             ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
             990
@@ -355,6 +1076,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn metered_generator_partial_draw_and_refuel() {
+        // Uranium costs 1_000 BTU/unit, so a reserve of 2_500 can only afford 2 of the 10 units
+        // requested.
+        let mg = MeteredGenerator::<Uranium, _>::new(NuclearReactor, 2_500);
+        assert_eq!(mg.get_fuel(), 2_500);
+
+        let energy = mg.provide_energy(FuelContainer::<Uranium>::new(10));
+        assert_eq!(energy.to_btu(), 1_980); // NuclearReactor's 99% of 2 units.
+        assert_eq!(mg.get_fuel(), 500);
+
+        assert!(mg.refuel(1_000));
+        assert_eq!(mg.get_fuel(), 1_500);
+
+        mg.set_fuel(0);
+        assert!(!mg.refuel(500));
+        assert_eq!(mg.get_fuel(), 500);
+    }
+
+    #[test]
+    fn metered_generator_large_draw_does_not_wrap_consumed() {
+        // Regression test: `density * amount` can exceed `i64::MAX` for ordinary `u32` inputs
+        // (not just contrived ones), so tracking `consumed` as a signed total and casting the
+        // per-call debit down to `i64` could wrap it negative, which a `get_fuel` built on
+        // `saturating_add_signed` would then silently read back as a full reserve. `consumed`
+        // must stay an unsigned running total that only ever grows.
+        struct MaxDensity;
+        impl Fuel for MaxDensity {
+            type Output = BTU;
+            fn energy_density() -> Self::Output {
+                u32::MAX
+            }
+        }
+
+        let mg = MeteredGenerator::<MaxDensity, _>::new(OmniGenerator::<100>, u64::MAX);
+        mg.provide_energy(FuelContainer::<MaxDensity>::new(u32::MAX));
+
+        // `u32::MAX * u32::MAX` was drawable in one go, so almost the entire `u64::MAX` reserve
+        // should now be spent, not silently reported as untouched.
+        assert_eq!(mg.get_fuel(), 8_589_934_590);
+    }
+
+    #[test]
+    fn condensing_boiler_below_dewpoint() {
+        let boiler = CondensingBoiler::<NaturalGas>::new(0.0);
+        // At t == 0, the quadratic term vanishes, leaving exactly the base 0.979 efficiency.
+        let energy = boiler.provide_energy_at_return_temp(FuelContainer::<NaturalGas>::new(1), 0.0);
+        assert_eq!(energy, 979);
+    }
+
+    #[test]
+    fn condensing_boiler_offset_reduces_output() {
+        let boiler = CondensingBoiler::<NaturalGas>::new(0.1);
+        let energy = boiler.provide_energy_at_return_temp(FuelContainer::<NaturalGas>::new(1), 0.0);
+        assert_eq!(energy, 879);
+    }
+
+    #[test]
+    fn condensing_boiler_falls_off_above_dewpoint() {
+        let boiler = CondensingBoiler::<NaturalGas>::new(0.0);
+        let below = boiler.provide_energy_at_return_temp(FuelContainer::<NaturalGas>::new(1), 52.2);
+        let above = boiler.provide_energy_at_return_temp(FuelContainer::<NaturalGas>::new(1), 80.0);
+        assert!(above < below);
+    }
+
+    #[test]
+    fn cogen_splits_usable_energy_by_power_to_heat_ratio() {
+        // Uranium: 1000 BTU/unit, 10 units -> 10_000 BTU fuel energy. 90% efficient -> 9_000 BTU
+        // usable, split 70/30 between electrical and heat.
+        let chp = CombinedHeatPower::<Uranium, 70>::new(90);
+        let output = chp.provide_cogen(FuelContainer::<Uranium>::new(10));
+        assert_eq!(output.electrical, 6_300);
+        assert_eq!(output.heat, 2_700);
+        assert!(output.electrical + output.heat <= 10_000);
+    }
+
+    #[test]
+    fn cogen_provide_energy_reports_only_electrical() {
+        let chp = CombinedHeatPower::<Uranium, 70>::new(90);
+        let energy = chp.provide_energy(FuelContainer::<Uranium>::new(10));
+        assert_eq!(energy.to_btu(), 6_300);
+    }
+
     #[test]
     fn mixed_1() {
         assert_eq!(
@@ -371,6 +1175,44 @@ mod tests {
             Mixed::<Diesel, LithiumBattery>::energy_density()
         );
     }
+    #[test]
+    fn blend_averages_by_amount() {
+        // 10 units of Diesel (100 BTU/unit) blended with 30 units of Uranium (1000 BTU/unit)
+        // should weight 3:1 towards Uranium's density.
+        let blended = blend(
+            FuelContainer::<Diesel>::new(10),
+            FuelContainer::<Uranium>::new(30),
+            50,
+        )
+        .unwrap();
+        assert_eq!(blended.amount, 40);
+        // (10 * 100 + 30 * 1000) / 40 = 775 BTU/unit.
+        assert_eq!(BTU::from_canonical(blended.energy_density()), 775);
+    }
+
+    #[test]
+    fn blend_falls_back_to_ratio_when_both_empty() {
+        let blended = blend(
+            FuelContainer::<Diesel>::new(0),
+            FuelContainer::<Uranium>::new(0),
+            25,
+        )
+        .unwrap();
+        assert_eq!(blended.amount, 0);
+        // 25% of Diesel's 100 BTU/unit + 75% of Uranium's 1000 BTU/unit.
+        assert_eq!(BTU::from_canonical(blended.energy_density()), 775);
+    }
+
+    #[test]
+    fn blend_rejects_mismatched_renewability() {
+        let result = blend(
+            FuelContainer::<Diesel>::new(10),
+            FuelContainer::<LithiumBattery>::new(10),
+            50,
+        );
+        assert_eq!(result.err(), Some(BlendError::Incompatible));
+    }
+
     #[test]
     fn green_should_work() {
         let green_engine = GreenEngine::<LithiumBattery>(PhantomData);